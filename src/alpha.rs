@@ -0,0 +1,203 @@
+use std::marker::PhantomData;
+use std::mem;
+use std::slice;
+use num;
+use approx;
+use channel::{NormalBoundedChannel, ColorChannel, BoundedChannelScalarTraits};
+use color::{Color, Color4, Invert, Bounded, Lerp, Flatten};
+
+pub struct AlphaTag<T>(PhantomData<T>);
+
+/// Adds an opacity channel to any `Color`, mirroring the RGBA/HSVA pattern in `cgmath` and
+/// the `has_alpha` distinction in the `image` crate. Wraps the inner color verbatim and keeps
+/// its own `NormalBoundedChannel<T>` for alpha, so existing color types don't need a second,
+/// alpha-aware copy of themselves.
+///
+/// `#[repr(C)]` and field order (color, then alpha) are load-bearing: `Flatten` reinterprets
+/// `Alpha<C, T>` as a contiguous `&[T]` the same way `Rgb<T>` does, so the alpha channel lands
+/// right after the inner color's channels.
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, PartialOrd, Eq, Ord, Hash)]
+pub struct Alpha<C, T> {
+    color: C,
+    alpha: NormalBoundedChannel<T>,
+}
+
+impl<C, T> Alpha<C, T>
+    where T: BoundedChannelScalarTraits
+{
+    pub fn from_color_and_alpha(color: C, alpha: T) -> Self {
+        Alpha {
+            color: color,
+            alpha: NormalBoundedChannel::new(alpha),
+        }
+    }
+    pub fn color(&self) -> &C {
+        &self.color
+    }
+    pub fn alpha(&self) -> T {
+        self.alpha.value()
+    }
+    pub fn set_alpha(&mut self, val: T) {
+        self.alpha = NormalBoundedChannel::new(val);
+    }
+    pub fn split(self) -> (C, T) {
+        let alpha = self.alpha.value();
+        (self.color, alpha)
+    }
+}
+
+impl<C, T> Color for Alpha<C, T>
+    where C: Color,
+          T: BoundedChannelScalarTraits
+{
+    type Tag = AlphaTag<C::Tag>;
+    type ChannelsTuple = (C::ChannelsTuple, T);
+
+    fn num_channels() -> u32 {
+        C::num_channels() + 1
+    }
+    fn from_tuple(values: Self::ChannelsTuple) -> Self {
+        Alpha::from_color_and_alpha(C::from_tuple(values.0), values.1)
+    }
+    fn to_tuple(self) -> Self::ChannelsTuple {
+        let (color, alpha) = self.split();
+        (color.to_tuple(), alpha)
+    }
+}
+
+impl<C, T> Color4 for Alpha<C, T>
+    where C: Color,
+          T: BoundedChannelScalarTraits
+{
+}
+
+impl<C, T> Invert for Alpha<C, T>
+    where C: Invert,
+          T: BoundedChannelScalarTraits
+{
+    impl_color_invert!(Alpha {color, alpha});
+}
+
+impl<C, T> Bounded for Alpha<C, T>
+    where C: Bounded,
+          T: BoundedChannelScalarTraits
+{
+    impl_color_bounded!(Alpha {color, alpha});
+}
+
+impl<C, T, P> Lerp for Alpha<C, T>
+    where C: Lerp<Position = P>,
+          T: BoundedChannelScalarTraits + Lerp<Position = P>,
+          P: num::Float
+{
+    type Position = P;
+    impl_color_lerp_square!(Alpha {color, alpha});
+}
+
+impl<C, T> Flatten for Alpha<C, T>
+    where C: Flatten<ScalarFormat = T>,
+          T: BoundedChannelScalarTraits
+{
+    type ScalarFormat = T;
+
+    fn as_slice(&self) -> &[T] {
+        unsafe {
+            let ptr: *const T = mem::transmute(self);
+            slice::from_raw_parts(ptr, Self::num_channels() as usize)
+        }
+    }
+    fn from_slice(values: &[T]) -> Self {
+        let (color_chans, alpha_chan) = values.split_at(values.len() - 1);
+        Alpha::from_color_and_alpha(C::from_slice(color_chans), alpha_chan[0].clone())
+    }
+}
+
+// `ApproxEq` and `Default` stay hand-rolled rather than going through `impl_approx_eq!`/
+// `impl_color_default!` (see `ycbcr::bare_ycbcr`): those macros are built around fields that
+// are all crate `ColorChannel` wrappers of the same backing scalar, reading each one's
+// `.value()` or rebuilding it via a named wrapper type. `color` here is an arbitrary nested
+// `Color`, not a `ColorChannel`, so it needs `C`'s own `ApproxEq`/`Default` impl directly.
+impl<C, T> approx::ApproxEq for Alpha<C, T>
+    where C: approx::ApproxEq<Epsilon = T::Epsilon>,
+          T: BoundedChannelScalarTraits + approx::ApproxEq,
+          T::Epsilon: Clone
+{
+    type Epsilon = T::Epsilon;
+
+    fn default_epsilon() -> Self::Epsilon {
+        T::default_epsilon()
+    }
+    fn default_max_relative() -> Self::Epsilon {
+        T::default_max_relative()
+    }
+    fn default_max_ulps() -> u32 {
+        T::default_max_ulps()
+    }
+    fn relative_eq(&self,
+                    other: &Self,
+                    epsilon: Self::Epsilon,
+                    max_relative: Self::Epsilon)
+                    -> bool {
+        self.color.relative_eq(&other.color, epsilon.clone(), max_relative.clone()) &&
+        self.alpha().relative_eq(&other.alpha(), epsilon, max_relative)
+    }
+    fn ulps_eq(&self, other: &Self, epsilon: Self::Epsilon, max_ulps: u32) -> bool {
+        self.color.ulps_eq(&other.color, epsilon.clone(), max_ulps) &&
+        self.alpha().ulps_eq(&other.alpha(), epsilon, max_ulps)
+    }
+}
+
+impl<C, T> Default for Alpha<C, T>
+    where C: Default,
+          T: BoundedChannelScalarTraits + num::Zero
+{
+    fn default() -> Self {
+        Alpha {
+            color: C::default(),
+            alpha: NormalBoundedChannel::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::rgb::Rgb;
+    use ::color::*;
+
+    #[test]
+    fn test_construct() {
+        let c = Alpha::from_color_and_alpha(Rgb::from_channels(1.0_f32, 0.5, 0.0), 0.25_f32);
+        assert_eq!(c.color(), &Rgb::from_channels(1.0_f32, 0.5, 0.0));
+        assert_eq!(c.alpha(), 0.25_f32);
+
+        let (color, alpha) = c.split();
+        assert_eq!(color, Rgb::from_channels(1.0_f32, 0.5, 0.0));
+        assert_eq!(alpha, 0.25_f32);
+    }
+
+    #[test]
+    fn test_set_alpha() {
+        let mut c = Alpha::from_color_and_alpha(Rgb::from_channels(0u8, 0, 0), 10u8);
+        c.set_alpha(200u8);
+        assert_eq!(c.alpha(), 200u8);
+    }
+
+    #[test]
+    fn test_lerp() {
+        let c1 = Alpha::from_color_and_alpha(Rgb::from_channels(0.0_f32, 0.0, 0.0), 0.0_f32);
+        let c2 = Alpha::from_color_and_alpha(Rgb::from_channels(1.0_f32, 1.0, 1.0), 1.0_f32);
+
+        let mid = c1.lerp(&c2, 0.5_f32);
+        assert_ulps_eq!(mid.color().clone(), Rgb::from_channels(0.5_f32, 0.5, 0.5));
+        assert_ulps_eq!(mid.alpha(), 0.5_f32);
+    }
+
+    #[test]
+    fn test_flatten_round_trip() {
+        let c = Alpha::from_color_and_alpha(Rgb::from_channels(1.0_f32, 0.5, 0.0), 0.25_f32);
+        assert_eq!(c.as_slice(), &[1.0_f32, 0.5, 0.0, 0.25]);
+        assert_eq!(Alpha::from_slice(c.as_slice()), c);
+    }
+}
@@ -0,0 +1,339 @@
+use num;
+use channel::BoundedChannelScalarTraits;
+use color::Color;
+use alpha::Alpha;
+use rgb::Rgb;
+
+/// The classic Porter-Duff alpha compositing operators.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PorterDuff {
+    Over,
+    In,
+    Out,
+    Atop,
+    Xor,
+    Plus,
+}
+
+/// A way to combine a source color with a backdrop color: either plain Porter-Duff
+/// compositing, or one of the separable blend modes (applied to the unpremultiplied colors
+/// and then composited with the standard source-over alpha formula).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BlendMode {
+    PorterDuff(PorterDuff),
+    Multiply,
+    Screen,
+    Overlay,
+    Darken,
+    Lighten,
+    HardLight,
+    SoftLight,
+    Difference,
+    Exclusion,
+}
+
+/// Combines two colors of the same type according to a `BlendMode`.
+pub trait Blend {
+    /// Blends `self` (the source, on top) with `backdrop`, returning the composited result.
+    fn blend_with(self, backdrop: Self, mode: BlendMode) -> Self;
+}
+
+/// Requires `self` and `backdrop` to hold premultiplied-alpha colors, i.e. each channel already
+/// scaled by its own alpha (`Alpha::from_color_and_alpha` stores whatever color it's given
+/// verbatim, so callers building a color from a straight/unassociated value must premultiply it
+/// themselves before blending). Separable modes (`Multiply`, `Screen`, ...) unpremultiply
+/// internally to apply the mode to straight colors, then recomposite premultiplied.
+impl<T> Blend for Alpha<Rgb<T>, T>
+    where T: num::Float + BoundedChannelScalarTraits
+{
+    fn blend_with(self, backdrop: Self, mode: BlendMode) -> Self {
+        match mode {
+            BlendMode::PorterDuff(op) => porter_duff(self, backdrop, op),
+            BlendMode::Multiply => separable_blend(self, backdrop, multiply),
+            BlendMode::Screen => separable_blend(self, backdrop, screen),
+            BlendMode::Overlay => separable_blend(self, backdrop, overlay),
+            BlendMode::Darken => separable_blend(self, backdrop, darken),
+            BlendMode::Lighten => separable_blend(self, backdrop, lighten),
+            BlendMode::HardLight => separable_blend(self, backdrop, hard_light),
+            BlendMode::SoftLight => separable_blend(self, backdrop, soft_light),
+            BlendMode::Difference => separable_blend(self, backdrop, difference),
+            BlendMode::Exclusion => separable_blend(self, backdrop, exclusion),
+        }
+    }
+}
+
+fn transparent<T>() -> Alpha<Rgb<T>, T>
+    where T: num::Float + BoundedChannelScalarTraits
+{
+    Alpha::from_color_and_alpha(Rgb::from_channels(T::zero(), T::zero(), T::zero()), T::zero())
+}
+
+fn porter_duff_coefficients<T>(op: PorterDuff, src_alpha: T, backdrop_alpha: T) -> (T, T)
+    where T: num::Float
+{
+    let one = T::one();
+    let zero = T::zero();
+    match op {
+        PorterDuff::Over => (one, one - src_alpha),
+        PorterDuff::In => (backdrop_alpha, zero),
+        PorterDuff::Out => (one - backdrop_alpha, zero),
+        PorterDuff::Atop => (backdrop_alpha, one - src_alpha),
+        PorterDuff::Xor => (one - backdrop_alpha, one - src_alpha),
+        PorterDuff::Plus => (one, one),
+    }
+}
+
+/// Composites premultiplied `src` over/in/out/atop/xor/plus premultiplied `backdrop`:
+/// `Co = Cs*Fa + Cb*Fb`, `ao = as*Fa + ab*Fb`, per the Porter-Duff coefficient table.
+fn porter_duff<T>(src: Alpha<Rgb<T>, T>, backdrop: Alpha<Rgb<T>, T>, op: PorterDuff) -> Alpha<Rgb<T>, T>
+    where T: num::Float + BoundedChannelScalarTraits
+{
+    let src_alpha = src.alpha();
+    let backdrop_alpha = backdrop.alpha();
+    let (fa, fb) = porter_duff_coefficients(op, src_alpha, backdrop_alpha);
+
+    let out_alpha = src_alpha * fa + backdrop_alpha * fb;
+    if out_alpha <= T::zero() {
+        return transparent();
+    }
+
+    let (sr, sg, sb) = src.color().clone().to_tuple();
+    let (br, bg, bb) = backdrop.color().clone().to_tuple();
+
+    let out_color = Rgb::from_channels(sr * fa + br * fb, sg * fa + bg * fb, sb * fa + bb * fb);
+    Alpha::from_color_and_alpha(out_color, out_alpha)
+}
+
+fn unpremultiply<T>(premultiplied: T, alpha: T) -> T
+    where T: num::Float
+{
+    if alpha > T::zero() {
+        premultiplied / alpha
+    } else {
+        T::zero()
+    }
+}
+
+/// Blends unpremultiplied `src` and `backdrop` channel-by-channel with `f`, then composites
+/// the blended color back over `backdrop` with the standard source-over formula:
+/// `Co = as*(1-ab)*Cs + as*ab*B(Cb,Cs) + (1-as)*ab*Cb`, `ao = as + ab*(1-as)`.
+fn separable_blend<T, F>(src: Alpha<Rgb<T>, T>, backdrop: Alpha<Rgb<T>, T>, f: F) -> Alpha<Rgb<T>, T>
+    where T: num::Float + BoundedChannelScalarTraits,
+          F: Fn(T, T) -> T
+{
+    let src_alpha = src.alpha();
+    let backdrop_alpha = backdrop.alpha();
+    let out_alpha = src_alpha + backdrop_alpha * (T::one() - src_alpha);
+    if out_alpha <= T::zero() {
+        return transparent();
+    }
+
+    let (spr, spg, spb) = src.color().clone().to_tuple();
+    let (bpr, bpg, bpb) = backdrop.color().clone().to_tuple();
+
+    let blend_channel = |s_premul: T, b_premul: T| {
+        let s = unpremultiply(s_premul, src_alpha);
+        let b = unpremultiply(b_premul, backdrop_alpha);
+        src_alpha * (T::one() - backdrop_alpha) * s + src_alpha * backdrop_alpha * f(s, b) +
+        (T::one() - src_alpha) * backdrop_alpha * b
+    };
+
+    let out_color = Rgb::from_channels(blend_channel(spr, bpr),
+                                        blend_channel(spg, bpg),
+                                        blend_channel(spb, bpb));
+    Alpha::from_color_and_alpha(out_color, out_alpha)
+}
+
+fn multiply<T: num::Float>(a: T, b: T) -> T {
+    a * b
+}
+
+fn screen<T: num::Float>(a: T, b: T) -> T {
+    a + b - a * b
+}
+
+fn darken<T: num::Float>(a: T, b: T) -> T {
+    a.min(b)
+}
+
+fn lighten<T: num::Float>(a: T, b: T) -> T {
+    a.max(b)
+}
+
+fn hard_light<T: num::Float>(a: T, b: T) -> T {
+    let half: T = num::cast(0.5).unwrap();
+    let two: T = num::cast(2.0).unwrap();
+    if a <= half {
+        two * a * b
+    } else {
+        T::one() - two * (T::one() - a) * (T::one() - b)
+    }
+}
+
+fn overlay<T: num::Float>(a: T, b: T) -> T {
+    hard_light(b, a)
+}
+
+fn soft_light_d<T: num::Float>(b: T) -> T {
+    let quarter: T = num::cast(0.25).unwrap();
+    if b <= quarter {
+        let twelve: T = num::cast(12.0).unwrap();
+        let sixteen: T = num::cast(16.0).unwrap();
+        let four: T = num::cast(4.0).unwrap();
+        ((sixteen * b - twelve) * b + four) * b
+    } else {
+        b.sqrt()
+    }
+}
+
+fn soft_light<T: num::Float>(a: T, b: T) -> T {
+    let half: T = num::cast(0.5).unwrap();
+    let two: T = num::cast(2.0).unwrap();
+    if a <= half {
+        b - (T::one() - two * a) * b * (T::one() - b)
+    } else {
+        b + (two * a - T::one()) * (soft_light_d(b) - b)
+    }
+}
+
+fn difference<T: num::Float>(a: T, b: T) -> T {
+    (a - b).abs()
+}
+
+fn exclusion<T: num::Float>(a: T, b: T) -> T {
+    let two: T = num::cast(2.0).unwrap();
+    a + b - two * a * b
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn opaque(r: f32, g: f32, b: f32) -> Alpha<Rgb<f32>, f32> {
+        Alpha::from_color_and_alpha(Rgb::from_channels(r, g, b), 1.0)
+    }
+
+    #[test]
+    fn test_over_opaque_is_source() {
+        let src = opaque(1.0, 0.0, 0.0);
+        let backdrop = opaque(0.0, 1.0, 0.0);
+        let out = src.blend_with(backdrop, BlendMode::PorterDuff(PorterDuff::Over));
+        assert_ulps_eq!(out.color().clone(), Rgb::from_channels(1.0_f32, 0.0, 0.0));
+        assert_ulps_eq!(out.alpha(), 1.0_f32);
+    }
+
+    #[test]
+    fn test_over_half_alpha_blends_toward_backdrop() {
+        // Straight red (1,0,0) at alpha 0.5, stored premultiplied as (0.5,0,0).
+        let src = Alpha::from_color_and_alpha(Rgb::from_channels(0.5_f32, 0.0, 0.0), 0.5);
+        let backdrop = opaque(0.0, 0.0, 1.0);
+        let out = src.blend_with(backdrop, BlendMode::PorterDuff(PorterDuff::Over));
+        assert_ulps_eq!(out.alpha(), 1.0_f32);
+        assert_ulps_eq!(out.color().clone(), Rgb::from_channels(0.5_f32, 0.0, 0.5));
+    }
+
+    #[test]
+    fn test_in_clips_to_backdrop_shape() {
+        let src = opaque(1.0, 1.0, 1.0);
+        let backdrop = Alpha::from_color_and_alpha(Rgb::from_channels(0.0_f32, 0.0, 0.0), 0.25);
+        let out = src.blend_with(backdrop, BlendMode::PorterDuff(PorterDuff::In));
+        assert_ulps_eq!(out.alpha(), 0.25_f32);
+    }
+
+    #[test]
+    fn test_zero_alpha_result_is_transparent() {
+        let src = Alpha::from_color_and_alpha(Rgb::from_channels(1.0_f32, 0.0, 0.0), 0.0);
+        let backdrop = Alpha::from_color_and_alpha(Rgb::from_channels(0.0_f32, 1.0, 0.0), 0.0);
+        let out = src.blend_with(backdrop, BlendMode::PorterDuff(PorterDuff::Over));
+        assert_ulps_eq!(out.alpha(), 0.0_f32);
+        assert_ulps_eq!(out.color().clone(), Rgb::from_channels(0.0_f32, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_multiply_opaque() {
+        let src = opaque(0.5, 1.0, 0.2);
+        let backdrop = opaque(0.5, 0.5, 1.0);
+        let out = src.blend_with(backdrop, BlendMode::Multiply);
+        assert_ulps_eq!(out.color().clone(), Rgb::from_channels(0.25_f32, 0.5, 0.2));
+    }
+
+    #[test]
+    fn test_screen_opaque() {
+        let src = opaque(0.2, 0.0, 1.0);
+        let backdrop = opaque(0.5, 0.5, 1.0);
+        let out = src.blend_with(backdrop, BlendMode::Screen);
+        assert_ulps_eq!(out.color().clone(), Rgb::from_channels(0.6_f32, 0.5, 1.0));
+    }
+
+    #[test]
+    fn test_difference_opaque() {
+        let src = opaque(0.2, 0.8, 0.5);
+        let backdrop = opaque(0.5, 0.3, 0.5);
+        let out = src.blend_with(backdrop, BlendMode::Difference);
+        assert_ulps_eq!(out.color().clone(), Rgb::from_channels(0.3_f32, 0.5, 0.0));
+    }
+
+    #[test]
+    fn test_multiply_partial_alpha_unpremultiplies_before_blending() {
+        // Straight src (0.8,0.4,0.0) at alpha 0.5, stored premultiplied as (0.4,0.2,0.0).
+        let src = Alpha::from_color_and_alpha(Rgb::from_channels(0.4_f32, 0.2, 0.0), 0.5);
+        // Straight backdrop (0.2,0.6,1.0) at alpha 0.5, stored premultiplied as (0.1,0.3,0.5).
+        let backdrop = Alpha::from_color_and_alpha(Rgb::from_channels(0.1_f32, 0.3, 0.5), 0.5);
+        let out = src.blend_with(backdrop, BlendMode::Multiply);
+        assert_ulps_eq!(out.alpha(), 0.75_f32);
+        assert_ulps_eq!(out.color().clone(), Rgb::from_channels(0.29_f32, 0.31, 0.25));
+    }
+
+    // Straight src (1, 0, 0.5) and straight backdrop (0, 1, 0.5), each at alpha 0.5, stored
+    // premultiplied as (0.5, 0, 0.25) and (0, 0.5, 0.25). Per channel this pins down an
+    // asymmetric mode's `f(s, b)` argument order against the unpremultiply path: if `s` and
+    // `b` were swapped, the first two (asymmetric) channels below would disagree.
+    fn asymmetric_src() -> Alpha<Rgb<f32>, f32> {
+        Alpha::from_color_and_alpha(Rgb::from_channels(0.5_f32, 0.0, 0.25), 0.5)
+    }
+    fn asymmetric_backdrop() -> Alpha<Rgb<f32>, f32> {
+        Alpha::from_color_and_alpha(Rgb::from_channels(0.0_f32, 0.5, 0.25), 0.5)
+    }
+
+    #[test]
+    fn test_darken_partial_alpha() {
+        let out = asymmetric_src().blend_with(asymmetric_backdrop(), BlendMode::Darken);
+        assert_ulps_eq!(out.alpha(), 0.75_f32);
+        assert_ulps_eq!(out.color().clone(), Rgb::from_channels(0.25_f32, 0.25, 0.375));
+    }
+
+    #[test]
+    fn test_lighten_partial_alpha() {
+        let out = asymmetric_src().blend_with(asymmetric_backdrop(), BlendMode::Lighten);
+        assert_ulps_eq!(out.alpha(), 0.75_f32);
+        assert_ulps_eq!(out.color().clone(), Rgb::from_channels(0.5_f32, 0.5, 0.375));
+    }
+
+    #[test]
+    fn test_hard_light_partial_alpha() {
+        let out = asymmetric_src().blend_with(asymmetric_backdrop(), BlendMode::HardLight);
+        assert_ulps_eq!(out.alpha(), 0.75_f32);
+        assert_ulps_eq!(out.color().clone(), Rgb::from_channels(0.5_f32, 0.25, 0.375));
+    }
+
+    #[test]
+    fn test_overlay_partial_alpha() {
+        let out = asymmetric_src().blend_with(asymmetric_backdrop(), BlendMode::Overlay);
+        assert_ulps_eq!(out.alpha(), 0.75_f32);
+        assert_ulps_eq!(out.color().clone(), Rgb::from_channels(0.25_f32, 0.5, 0.375));
+    }
+
+    #[test]
+    fn test_soft_light_partial_alpha() {
+        let out = asymmetric_src().blend_with(asymmetric_backdrop(), BlendMode::SoftLight);
+        assert_ulps_eq!(out.alpha(), 0.75_f32);
+        assert_ulps_eq!(out.color().clone(), Rgb::from_channels(0.25_f32, 0.5, 0.375));
+    }
+
+    #[test]
+    fn test_exclusion_partial_alpha() {
+        let out = asymmetric_src().blend_with(asymmetric_backdrop(), BlendMode::Exclusion);
+        assert_ulps_eq!(out.alpha(), 0.75_f32);
+        assert_ulps_eq!(out.color().clone(), Rgb::from_channels(0.5_f32, 0.5, 0.375));
+    }
+}
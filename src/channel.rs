@@ -0,0 +1,201 @@
+use color;
+
+/// A scalar type that can be used to back a color channel.
+///
+/// Implementors surface the `[min_bound, max_bound]` range that a "full strength" channel value
+/// spans for that scalar, so code like `color_cast` can remap between, say, `u8` and `f32`
+/// without hardcoding `0`/`255` anywhere.
+pub trait BoundedChannelScalarTraits
+    : Copy + Clone + PartialEq + PartialOrd + Default + ::std::fmt::Debug + ::std::fmt::Display +
+      ::num::NumCast + ::std::ops::Sub<Output = Self>
+    {
+    fn min_bound() -> Self;
+    fn max_bound() -> Self;
+    /// Round a rescaled `f64` value before casting it back down to `Self`. Integer channels
+    /// round to the nearest whole number; float channels are already in their native
+    /// representation and are passed through unchanged.
+    fn round_for_cast(value: f64) -> f64;
+}
+
+macro_rules! impl_bounded_channel_scalar_int {
+    ($ty:ty, $min:expr, $max:expr) => {
+        impl BoundedChannelScalarTraits for $ty {
+            #[inline]
+            fn min_bound() -> Self {
+                $min
+            }
+            #[inline]
+            fn max_bound() -> Self {
+                $max
+            }
+            #[inline]
+            fn round_for_cast(value: f64) -> f64 {
+                value.round()
+            }
+        }
+    }
+}
+
+macro_rules! impl_bounded_channel_scalar_float {
+    ($ty:ty) => {
+        impl BoundedChannelScalarTraits for $ty {
+            #[inline]
+            fn min_bound() -> Self {
+                0.0
+            }
+            #[inline]
+            fn max_bound() -> Self {
+                1.0
+            }
+            #[inline]
+            fn round_for_cast(value: f64) -> f64 {
+                value
+            }
+        }
+    }
+}
+
+impl_bounded_channel_scalar_int!(u8, 0, 255);
+impl_bounded_channel_scalar_int!(u16, 0, 65535);
+impl_bounded_channel_scalar_int!(u32, 0, 4294967295);
+impl_bounded_channel_scalar_float!(f32);
+impl_bounded_channel_scalar_float!(f64);
+
+/// Marker for scalars backing a channel that is always non-negative, e.g. luma.
+pub trait PosNormalChannelScalar: BoundedChannelScalarTraits {}
+impl<T> PosNormalChannelScalar for T where T: BoundedChannelScalarTraits {}
+
+/// Marker for scalars backing a channel that may swing negative, e.g. chroma.
+pub trait NormalChannelScalar: BoundedChannelScalarTraits {}
+impl<T> NormalChannelScalar for T where T: BoundedChannelScalarTraits {}
+
+/// Common operations shared by every bounded channel wrapper (`BoundedChannel`,
+/// `NormalBoundedChannel`, `PosNormalBoundedChannel`, ...). Brought into scope wherever
+/// `.clamp()`/`.invert()`/`.normalize()`/`.is_normalized()` are called on a channel.
+pub trait ColorChannel: Sized + Clone {
+    type Format: BoundedChannelScalarTraits;
+
+    fn min_bound() -> Self::Format {
+        Self::Format::min_bound()
+    }
+    fn max_bound() -> Self::Format {
+        Self::Format::max_bound()
+    }
+
+    fn value(&self) -> Self::Format;
+    fn clamp(self, min: Self::Format, max: Self::Format) -> Self;
+    fn invert(self) -> Self;
+    fn normalize(self) -> Self;
+    fn is_normalized(&self) -> bool;
+}
+
+macro_rules! impl_color_channel {
+    ($name:ident) => {
+        #[repr(transparent)]
+        #[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Eq, Ord, Hash, Default)]
+        pub struct $name<T>(pub T);
+
+        impl<T> $name<T> {
+            pub fn new(value: T) -> Self {
+                $name(value)
+            }
+        }
+
+        impl<T> ::std::fmt::Display for $name<T>
+            where T: ::std::fmt::Display
+        {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+
+        impl<T> ColorChannel for $name<T>
+            where T: BoundedChannelScalarTraits
+        {
+            type Format = T;
+
+            fn value(&self) -> T {
+                self.0.clone()
+            }
+
+            fn clamp(self, min: T, max: T) -> Self {
+                if self.0 < min {
+                    $name(min)
+                } else if self.0 > max {
+                    $name(max)
+                } else {
+                    self
+                }
+            }
+
+            fn invert(self) -> Self {
+                let min = ::num::cast::<_, f64>(T::min_bound()).unwrap();
+                let max = ::num::cast::<_, f64>(T::max_bound()).unwrap();
+                let val = ::num::cast::<_, f64>(self.0).unwrap();
+                $name(::num::cast(T::round_for_cast(min + max - val)).unwrap())
+            }
+
+            fn normalize(self) -> Self {
+                self.clamp(T::min_bound(), T::max_bound())
+            }
+
+            fn is_normalized(&self) -> bool {
+                self.0 >= T::min_bound() && self.0 <= T::max_bound()
+            }
+        }
+
+        impl<T> color::Lerp for $name<T>
+            where T: BoundedChannelScalarTraits + color::Lerp
+        {
+            type Position = <T as color::Lerp>::Position;
+            fn lerp(&self, right: &Self, pos: Self::Position) -> Self {
+                $name(self.0.lerp(&right.0, pos))
+            }
+        }
+    }
+}
+
+impl_color_channel!(BoundedChannel);
+impl_color_channel!(NormalBoundedChannel);
+impl_color_channel!(PosNormalBoundedChannel);
+
+/// Casts a single channel value from one backing scalar to another, remapping
+/// `[from_min, from_max]` to `[to_min, to_max]` and saturating rather than wrapping.
+pub fn cast_channel<From, To>(value: From) -> To
+    where From: BoundedChannelScalarTraits,
+          To: BoundedChannelScalarTraits
+{
+    let from_min = ::num::cast::<_, f64>(From::min_bound()).unwrap();
+    let from_max = ::num::cast::<_, f64>(From::max_bound()).unwrap();
+    let to_min = ::num::cast::<_, f64>(To::min_bound()).unwrap();
+    let to_max = ::num::cast::<_, f64>(To::max_bound()).unwrap();
+
+    let factor = (to_max - to_min) / (from_max - from_min);
+    let shift = to_min - from_min * factor;
+
+    let scaled = ::num::cast::<_, f64>(value).unwrap() * factor + shift;
+    let clamped = scaled.max(to_min).min(to_max);
+    ::num::cast(To::round_for_cast(clamped)).unwrap()
+}
+
+/// A channel format that can be cast to another format of the same kind.
+pub trait ChannelFormatCast<To>: BoundedChannelScalarTraits
+    where To: BoundedChannelScalarTraits
+{
+    fn channel_cast(self) -> To;
+}
+
+impl<From, To> ChannelFormatCast<To> for From
+    where From: BoundedChannelScalarTraits,
+          To: BoundedChannelScalarTraits
+{
+    fn channel_cast(self) -> To {
+        cast_channel(self)
+    }
+}
+
+/// Casts every channel of a channel wrapper (`BoundedChannel<T>`, ...) to a new backing scalar.
+pub trait ChannelCast {
+    type Output;
+    fn channel_cast(self) -> Self::Output;
+}
@@ -0,0 +1,89 @@
+use color::Color;
+use matrix::Matrix3;
+use white_point::WhitePoint;
+use xyz::Xyz;
+
+/// Which cone-response model to adapt through. Bradford is the most commonly used in color
+/// management (it's what ICC profiles default to); Von Kries and XYZ scaling are the classic
+/// alternatives.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AdaptationMethod {
+    Bradford,
+    VonKries,
+    XyzScaling,
+}
+
+impl AdaptationMethod {
+    fn cone_response_matrix(&self) -> Matrix3<f64> {
+        match *self {
+            AdaptationMethod::Bradford => {
+                Matrix3::new([[0.8951, 0.2664, -0.1614],
+                              [-0.7502, 1.7135, 0.0367],
+                              [0.0389, -0.0685, 1.0296]])
+            }
+            AdaptationMethod::VonKries => {
+                Matrix3::new([[0.40024, 0.70760, -0.08081],
+                              [-0.22630, 1.16532, 0.04570],
+                              [0.00000, 0.00000, 0.91822]])
+            }
+            AdaptationMethod::XyzScaling => Matrix3::identity(),
+        }
+    }
+}
+
+/// A precomputed chromatic adaptation transform between two reference white points, following
+/// palette's `chromatic_adaptation` module: the source and destination whites are projected
+/// into cone-response space, scaled component-wise, and projected back, giving a single 3x3
+/// matrix `A = M^-1 * D * M` that can be applied directly to any XYZ color.
+pub struct ChromaticAdaptation {
+    transform: Matrix3<f64>,
+}
+
+impl ChromaticAdaptation {
+    /// Builds the adaptation transform that maps colors under `src` to how they'd appear
+    /// under `dst`, using the given cone-response `method`.
+    pub fn new(src: WhitePoint, dst: WhitePoint, method: AdaptationMethod) -> Self {
+        let m = method.cone_response_matrix();
+        let m_inv = m.inverse();
+
+        let src_xyz = src.get_xyz().to_tuple();
+        let dst_xyz = dst.get_xyz().to_tuple();
+
+        let src_cone = m.transform_vector(src_xyz);
+        let dst_cone = m.transform_vector(dst_xyz);
+
+        let scale = Matrix3::diagonal((dst_cone.0 / src_cone.0,
+                                        dst_cone.1 / src_cone.1,
+                                        dst_cone.2 / src_cone.2));
+
+        ChromaticAdaptation { transform: m_inv.mul(&scale).mul(&m) }
+    }
+
+    /// Adapts an XYZ color from the source white point to the destination white point.
+    pub fn adapt(&self, color: Xyz<f64>) -> Xyz<f64> {
+        let (x, y, z) = self.transform.transform_vector(color.to_tuple());
+        Xyz::from_channels(x, y, z)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use approx::ApproxEq;
+
+    #[test]
+    fn test_identity_adaptation() {
+        let adapt = ChromaticAdaptation::new(WhitePoint::D65, WhitePoint::D65,
+                                              AdaptationMethod::Bradford);
+        let c = Xyz::from_channels(0.5, 0.4, 0.3);
+        assert_ulps_eq!(adapt.adapt(c), c);
+    }
+
+    #[test]
+    fn test_white_point_maps_to_white_point() {
+        let adapt = ChromaticAdaptation::new(WhitePoint::D65, WhitePoint::D50,
+                                              AdaptationMethod::Bradford);
+        let adapted = adapt.adapt(WhitePoint::D65.get_xyz());
+        assert_relative_eq!(adapted, WhitePoint::D50.get_xyz(), epsilon = 1e-6);
+    }
+}
@@ -1,4 +1,6 @@
 use num;
+use channel;
+use channel::BoundedChannelScalarTraits;
 
 pub trait Color: Clone + PartialEq {
     type Tag;
@@ -26,6 +28,16 @@ pub trait HomogeneousColor: Color {
 pub trait Color3: Color {}
 pub trait Color4: Color {}
 
+/// Like `HomogeneousColor`, but for colors whose channels share a scalar type without
+/// necessarily sharing the same valid range (e.g. `BareYCbCr`'s luma and chroma channels),
+/// so it only promises a flat view of the channels rather than `broadcast`/`clamp`.
+pub trait Flatten: Color {
+    type ScalarFormat;
+
+    fn as_slice(&self) -> &[Self::ScalarFormat];
+    fn from_slice(values: &[Self::ScalarFormat]) -> Self;
+}
+
 pub trait Lerp {
     type Position: num::Float;
     fn lerp(&self, right: &Self, pos: Self::Position) -> Self;
@@ -40,28 +52,23 @@ pub trait Bounded {
     fn is_normalized(&self) -> bool;
 }
 
-/*pub fn color_cast<To, From>(from: &From) -> To 
-        where From: Color + Color3,
-              To: Color<Tag=From::Tag> + Color3,
-              To::Component: num::NumCast,
-              From::Component: num::NumCast,
+/// Casts a color from one channel representation to another, e.g. `Rgb<u8>` to `Rgb<f32>`,
+/// rescaling each channel from the source scalar's `[min_bound, max_bound]` to the
+/// destination scalar's, the way `cgmath`'s `to_rgb_u8`/`to_rgb_u16`/`to_rgb_f32` family does.
+///
+/// `From` and `To` must be the same kind of color (same `Tag`) so only the channel format
+/// changes, e.g. `Rgb<u8> -> Rgb<f32>` is valid but `Rgb<u8> -> BareYCbCr<u8>` is not.
+pub fn color_cast<To, From>(from: &From) -> To
+    where From: HomogeneousColor,
+          From::ChannelFormat: BoundedChannelScalarTraits,
+          To: HomogeneousColor<Tag = From::Tag>,
+          To::ChannelFormat: BoundedChannelScalarTraits
 {
-
-    let to_scale = To::Component::max() - To::Component::min();
-    let from_scale = From::Component::max() - From::Component::min();
-   
-    let shift = cast::<_,f64>(To::Component::min()).unwrap() 
-        - cast::<_,f64>(From::Component::min()).unwrap();
-    let factor: f64 = cast::<_,f64>(to_scale).unwrap() 
-        / cast::<_,f64>(from_scale).unwrap();
-
-    let mut out = [To::Component::default(); 3];
-    let vals = from.as_slice();
-
-    for i in 0..3 {
-        out[i] = cast::<_, To::Component>(
-            cast::<_,f64>(vals[i]).unwrap()*factor + shift).unwrap();
-    }
+    let out: Vec<_> = from.as_slice()
+        .iter()
+        .cloned()
+        .map(channel::cast_channel::<From::ChannelFormat, To::ChannelFormat>)
+        .collect();
 
     To::from_slice(&out)
-}*/
+}
@@ -0,0 +1,156 @@
+use num;
+use num::Zero;
+use color::Lerp;
+
+/// A multi-stop color gradient/ramp over any `C: Lerp`, built from a sorted list of
+/// `(position, color)` stops. Parallels palette's `Gradient` type, making the `Lerp` impls
+/// already on `Rgb`/`BareYCbCr` directly useful for sampling ramps.
+#[derive(Clone, Debug)]
+pub struct Gradient<C>
+    where C: Lerp
+{
+    stops: Vec<(C::Position, C)>,
+}
+
+impl<C> Gradient<C>
+    where C: Lerp + Clone
+{
+    /// Builds a gradient from its stops, sorting them by position. Panics if `stops` is empty.
+    pub fn new(mut stops: Vec<(C::Position, C)>) -> Self {
+        assert!(!stops.is_empty(), "a gradient needs at least one stop");
+        stops.sort_by(|a, b| a.0.partial_cmp(&b.0).expect("gradient stop position is NaN"));
+        Gradient { stops: stops }
+    }
+
+    /// Samples the gradient at `pos`, linearly interpolating between the two bracketing
+    /// stops. Positions below the first stop or above the last are clamped to that stop's
+    /// color, and a single-stop gradient returns that color everywhere.
+    pub fn get(&self, pos: C::Position) -> C {
+        let first = &self.stops[0];
+        if self.stops.len() == 1 || pos <= first.0 {
+            return first.1.clone();
+        }
+
+        let last = &self.stops[self.stops.len() - 1];
+        if pos >= last.0 {
+            return last.1.clone();
+        }
+
+        let upper_idx = self.stops
+            .iter()
+            .position(|stop| stop.0 >= pos)
+            .unwrap();
+        let &(lower_pos, ref lower_color) = &self.stops[upper_idx - 1];
+        let &(upper_pos, ref upper_color) = &self.stops[upper_idx];
+
+        let span = upper_pos - lower_pos;
+        let t = if span > C::Position::zero() {
+            (pos - lower_pos) / span
+        } else {
+            C::Position::zero()
+        };
+
+        lower_color.lerp(upper_color, t)
+    }
+
+    /// Samples `n` evenly spaced colors from the first stop's position to the last's
+    /// (inclusive).
+    pub fn take(&self, n: usize) -> GradientIter<'_, C> {
+        GradientIter {
+            gradient: self,
+            first: self.stops[0].0,
+            last: self.stops[self.stops.len() - 1].0,
+            n: n,
+            idx: 0,
+        }
+    }
+}
+
+/// Iterator over `n` evenly spaced samples of a `Gradient`, built by `Gradient::take`.
+pub struct GradientIter<'a, C>
+    where C: Lerp + 'a
+{
+    gradient: &'a Gradient<C>,
+    first: C::Position,
+    last: C::Position,
+    n: usize,
+    idx: usize,
+}
+
+impl<'a, C> Iterator for GradientIter<'a, C>
+    where C: Lerp + Clone
+{
+    type Item = C;
+
+    fn next(&mut self) -> Option<C> {
+        if self.idx >= self.n {
+            return None;
+        }
+
+        let pos = if self.n == 1 {
+            self.first
+        } else {
+            let t: C::Position = num::cast::<_, C::Position>(self.idx).unwrap() /
+                                  num::cast::<_, C::Position>(self.n - 1).unwrap();
+            self.first + (self.last - self.first) * t
+        };
+
+        self.idx += 1;
+        Some(self.gradient.get(pos))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::rgb::Rgb;
+
+    #[test]
+    fn test_get_endpoints() {
+        let gradient = Gradient::new(vec![(0.0_f32, Rgb::from_channels(0.0_f32, 0.0, 0.0)),
+                                           (1.0_f32, Rgb::from_channels(1.0_f32, 1.0, 1.0))]);
+
+        assert_eq!(gradient.get(0.0), Rgb::from_channels(0.0_f32, 0.0, 0.0));
+        assert_eq!(gradient.get(1.0), Rgb::from_channels(1.0_f32, 1.0, 1.0));
+        assert_ulps_eq!(gradient.get(0.5), Rgb::from_channels(0.5_f32, 0.5, 0.5));
+    }
+
+    #[test]
+    fn test_get_clamps_outside_range() {
+        let gradient = Gradient::new(vec![(0.25_f32, Rgb::from_channels(1.0_f32, 0.0, 0.0)),
+                                           (0.75_f32, Rgb::from_channels(0.0_f32, 0.0, 1.0))]);
+
+        assert_eq!(gradient.get(-1.0), Rgb::from_channels(1.0_f32, 0.0, 0.0));
+        assert_eq!(gradient.get(2.0), Rgb::from_channels(0.0_f32, 0.0, 1.0));
+    }
+
+    #[test]
+    fn test_single_stop() {
+        let gradient = Gradient::new(vec![(0.5_f32, Rgb::from_channels(0.2_f32, 0.4, 0.6))]);
+
+        assert_eq!(gradient.get(-10.0), Rgb::from_channels(0.2_f32, 0.4, 0.6));
+        assert_eq!(gradient.get(10.0), Rgb::from_channels(0.2_f32, 0.4, 0.6));
+    }
+
+    #[test]
+    fn test_multi_stop() {
+        let gradient = Gradient::new(vec![(0.0_f32, Rgb::from_channels(0.0_f32, 0.0, 0.0)),
+                                           (0.5_f32, Rgb::from_channels(1.0_f32, 0.0, 0.0)),
+                                           (1.0_f32, Rgb::from_channels(1.0_f32, 1.0, 0.0))]);
+
+        assert_ulps_eq!(gradient.get(0.25), Rgb::from_channels(0.5_f32, 0.0, 0.0));
+        assert_ulps_eq!(gradient.get(0.75), Rgb::from_channels(1.0_f32, 0.5, 0.0));
+    }
+
+    #[test]
+    fn test_take() {
+        let gradient = Gradient::new(vec![(0.0_f32, Rgb::from_channels(0.0_f32, 0.0, 0.0)),
+                                           (1.0_f32, Rgb::from_channels(1.0_f32, 1.0, 1.0))]);
+
+        let samples: Vec<_> = gradient.take(3).collect();
+        assert_eq!(samples.len(), 3);
+        assert_ulps_eq!(samples[0], Rgb::from_channels(0.0_f32, 0.0, 0.0));
+        assert_ulps_eq!(samples[1], Rgb::from_channels(0.5_f32, 0.5, 0.5));
+        assert_ulps_eq!(samples[2], Rgb::from_channels(1.0_f32, 1.0, 1.0));
+    }
+}
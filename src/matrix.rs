@@ -0,0 +1,94 @@
+use num;
+
+/// A 3x3 matrix over a floating point scalar, stored row-major.
+///
+/// This is the matrix machinery `YCbCrModel` uses for its forward/inverse transforms, reused
+/// here for chromatic adaptation's cone-response transforms.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Matrix3<T> {
+    pub m: [[T; 3]; 3],
+}
+
+impl<T> Matrix3<T>
+    where T: num::Float
+{
+    pub fn new(m: [[T; 3]; 3]) -> Self {
+        Matrix3 { m: m }
+    }
+
+    pub fn identity() -> Self {
+        let zero = T::zero();
+        let one = T::one();
+        Matrix3::new([[one, zero, zero], [zero, one, zero], [zero, zero, one]])
+    }
+
+    pub fn diagonal(d: (T, T, T)) -> Self {
+        let zero = T::zero();
+        Matrix3::new([[d.0, zero, zero], [zero, d.1, zero], [zero, zero, d.2]])
+    }
+
+    /// Applies this matrix to a column vector `v`.
+    pub fn transform_vector(&self, v: (T, T, T)) -> (T, T, T) {
+        let m = &self.m;
+        (m[0][0] * v.0 + m[0][1] * v.1 + m[0][2] * v.2,
+         m[1][0] * v.0 + m[1][1] * v.1 + m[1][2] * v.2,
+         m[2][0] * v.0 + m[2][1] * v.1 + m[2][2] * v.2)
+    }
+
+    pub fn mul(&self, other: &Matrix3<T>) -> Matrix3<T> {
+        let a = &self.m;
+        let b = &other.m;
+        let mut out = [[T::zero(); 3]; 3];
+        for i in 0..3 {
+            for j in 0..3 {
+                out[i][j] = a[i][0] * b[0][j] + a[i][1] * b[1][j] + a[i][2] * b[2][j];
+            }
+        }
+        Matrix3::new(out)
+    }
+
+    pub fn determinant(&self) -> T {
+        let m = &self.m;
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1]) -
+        m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0]) +
+        m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+
+    /// Inverts this matrix via the adjugate / determinant. Panics if the matrix is singular,
+    /// which none of the fixed adaptation matrices used by this crate are.
+    pub fn inverse(&self) -> Matrix3<T> {
+        let m = &self.m;
+        let det = self.determinant();
+        assert!(det != T::zero(), "matrix is not invertible");
+        let inv_det = T::one() / det;
+
+        Matrix3::new([[(m[1][1] * m[2][2] - m[1][2] * m[2][1]) * inv_det,
+                        (m[0][2] * m[2][1] - m[0][1] * m[2][2]) * inv_det,
+                        (m[0][1] * m[1][2] - m[0][2] * m[1][1]) * inv_det],
+                       [(m[1][2] * m[2][0] - m[1][0] * m[2][2]) * inv_det,
+                        (m[0][0] * m[2][2] - m[0][2] * m[2][0]) * inv_det,
+                        (m[0][2] * m[1][0] - m[0][0] * m[1][2]) * inv_det],
+                       [(m[1][0] * m[2][1] - m[1][1] * m[2][0]) * inv_det,
+                        (m[0][1] * m[2][0] - m[0][0] * m[2][1]) * inv_det,
+                        (m[0][0] * m[1][1] - m[0][1] * m[1][0]) * inv_det]])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_identity_transform() {
+        let m: Matrix3<f64> = Matrix3::identity();
+        assert_eq!(m.transform_vector((1.0, 2.0, 3.0)), (1.0, 2.0, 3.0));
+    }
+
+    #[test]
+    fn test_inverse() {
+        let m = Matrix3::new([[2.0, 0.0, 0.0], [0.0, 4.0, 0.0], [0.0, 0.0, 8.0]]);
+        let inv = m.inverse();
+        let round_trip = m.mul(&inv);
+        assert_eq!(round_trip.transform_vector((1.0, 1.0, 1.0)), (1.0, 1.0, 1.0));
+    }
+}
@@ -1,16 +1,21 @@
 use std::fmt;
 use std::mem;
+use std::slice;
+use std::error;
+use std::str::FromStr;
 use num;
 use num::cast;
 use approx;
 use channel::{BoundedChannel, ColorChannel, BoundedChannelScalarTraits};
 use color;
-use color::{Color, HomogeneousColor};
+use color::{Color, HomogeneousColor, Flatten};
 use convert;
 use angle;
+use alpha::Alpha;
 
 pub struct RgbTag;
 
+#[repr(C)]
 #[derive(Clone, Debug, PartialEq, PartialOrd, Eq, Ord, Hash)]
 pub struct Rgb<T> {
     pub red: BoundedChannel<T>,
@@ -83,19 +88,20 @@ impl<T> HomogeneousColor for Rgb<T>
     where T: BoundedChannelScalarTraits
 {
     type ChannelFormat = T;
-    // fn from_slice(values: &[T]) -> Self {
-    // Rgb {
-    // red: BoundedChannel(values[0].clone()),
-    // green: BoundedChannel(values[1].clone()),
-    // blue: BoundedChannel(values[2].clone())
-    // }
-    // }
-    // fn as_slice(&self) -> &[T] {
-    // unsafe {
-    // let ptr: *const T = mem::transmute(self);
-    // slice::from_raw_parts(ptr, Self::num_channels() as usize)
-    // }
-    // }
+
+    fn from_slice(values: &[T]) -> Self {
+        Rgb {
+            red: BoundedChannel(values[0].clone()),
+            green: BoundedChannel(values[1].clone()),
+            blue: BoundedChannel(values[2].clone()),
+        }
+    }
+    fn as_slice(&self) -> &[T] {
+        unsafe {
+            let ptr: *const T = mem::transmute(self);
+            slice::from_raw_parts(ptr, Self::num_channels() as usize)
+        }
+    }
     fn broadcast(value: T) -> Self {
         Rgb {
             red: BoundedChannel(value.clone()),
@@ -114,6 +120,19 @@ impl<T> HomogeneousColor for Rgb<T>
 
 impl<T> color::Color3 for Rgb<T> where T: BoundedChannelScalarTraits {}
 
+impl<T> Flatten for Rgb<T>
+    where T: BoundedChannelScalarTraits
+{
+    type ScalarFormat = T;
+
+    fn as_slice(&self) -> &[T] {
+        HomogeneousColor::as_slice(self)
+    }
+    fn from_slice(values: &[T]) -> Self {
+        HomogeneousColor::from_slice(values)
+    }
+}
+
 impl<T> color::Invert for Rgb<T>
     where T: BoundedChannelScalarTraits
 {
@@ -209,7 +228,120 @@ impl<T> fmt::Display for Rgb<T>
     }
 }
 
-fn get_hue_factor_and_ordered_chans<T>(color: &Rgb<T>) -> (T, T, T, T, T) 
+/// The ways `Rgb::from_hex`/`Alpha::from_hex`/`FromStr` can reject a hex color literal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FromHexError {
+    /// The string (after stripping a leading `#`) wasn't 3, 6, or 8 hex digits long.
+    InvalidLength,
+    /// The string contained a character outside `[0-9a-fA-F]`.
+    InvalidDigit,
+}
+
+impl fmt::Display for FromHexError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FromHexError::InvalidLength => {
+                write!(f, "hex color must be in #RGB, #RRGGBB, or #RRGGBBAA form")
+            }
+            FromHexError::InvalidDigit => write!(f, "hex color contains a non-hex digit"),
+        }
+    }
+}
+
+impl error::Error for FromHexError {
+    fn description(&self) -> &str {
+        match *self {
+            FromHexError::InvalidLength => "invalid hex color length",
+            FromHexError::InvalidDigit => "invalid hex digit",
+        }
+    }
+}
+
+fn parse_hex_channel(digits: &str) -> Result<u8, FromHexError> {
+    u8::from_str_radix(digits, 16).map_err(|_| FromHexError::InvalidDigit)
+}
+
+impl Rgb<u8> {
+    /// Parses a CSS/web-style hex color literal: `#RGB` or `#RRGGBB` (the leading `#` is
+    /// optional). The short form duplicates each digit, so `#0f3` is the same as `#00ff33`.
+    pub fn from_hex(hex: &str) -> Result<Self, FromHexError> {
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+        if !digits.is_ascii() {
+            return Err(FromHexError::InvalidDigit);
+        }
+
+        match digits.len() {
+            3 => {
+                let r = parse_hex_channel(&digits[0..1].repeat(2))?;
+                let g = parse_hex_channel(&digits[1..2].repeat(2))?;
+                let b = parse_hex_channel(&digits[2..3].repeat(2))?;
+                Ok(Rgb::from_channels(r, g, b))
+            }
+            6 => {
+                let r = parse_hex_channel(&digits[0..2])?;
+                let g = parse_hex_channel(&digits[2..4])?;
+                let b = parse_hex_channel(&digits[4..6])?;
+                Ok(Rgb::from_channels(r, g, b))
+            }
+            _ => Err(FromHexError::InvalidLength),
+        }
+    }
+
+    /// Formats this color as a lowercase `#RRGGBB` hex literal.
+    pub fn to_hex_string(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.red(), self.green(), self.blue())
+    }
+}
+
+impl FromStr for Rgb<u8> {
+    type Err = FromHexError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Rgb::from_hex(s)
+    }
+}
+
+impl Alpha<Rgb<u8>, u8> {
+    /// Parses a CSS/web-style hex color literal that also carries an alpha channel:
+    /// `#RRGGBBAA` (the leading `#` is optional). `#RGB`/`#RRGGBB` forms without an alpha
+    /// digit belong to `Rgb::from_hex` instead.
+    pub fn from_hex(hex: &str) -> Result<Self, FromHexError> {
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+        if !digits.is_ascii() {
+            return Err(FromHexError::InvalidDigit);
+        }
+
+        match digits.len() {
+            8 => {
+                let r = parse_hex_channel(&digits[0..2])?;
+                let g = parse_hex_channel(&digits[2..4])?;
+                let b = parse_hex_channel(&digits[4..6])?;
+                let a = parse_hex_channel(&digits[6..8])?;
+                Ok(Alpha::from_color_and_alpha(Rgb::from_channels(r, g, b), a))
+            }
+            _ => Err(FromHexError::InvalidLength),
+        }
+    }
+
+    /// Formats this color as a lowercase `#RRGGBBAA` hex literal.
+    pub fn to_hex_string(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}{:02x}",
+                self.color().red(),
+                self.color().green(),
+                self.color().blue(),
+                self.alpha())
+    }
+}
+
+impl FromStr for Alpha<Rgb<u8>, u8> {
+    type Err = FromHexError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Alpha::from_hex(s)
+    }
+}
+
+fn get_hue_factor_and_ordered_chans<T>(color: &Rgb<T>) -> (T, T, T, T, T)
     where T: BoundedChannelScalarTraits + num::Float
 {
     let mut scaling_factor = T::zero();
@@ -350,20 +482,75 @@ mod test {
             epsilon=1e-6);
     }
 
-    // #[test]
-    // fn color_cast() {
-    // let c = Rgb::from_channels(127, 0, 255);
-    // let c2 = color::color_cast::<Rgb<f32>, _>(&c);
-    // let c3 = color::color_cast::<Rgb<u8>, _>(&c2);
-    //
-    // assert_ulps_eq!(c2.red(), 127.0 / 255.0);
-    // assert_ulps_eq!(c2.green(), 0.0);
-    // assert_ulps_eq!(c2.blue(), 1.0);
-    //
-    // assert_eq!(c3.red(), 127);
-    // assert_eq!(c3.green(), 0);
-    // assert_eq!(c3.blue(), 255);
-    //
-    // println!("{}", c2);
-    // }
+    #[test]
+    fn test_color_cast() {
+        let c = Rgb::from_channels(127u8, 0, 255);
+        let c2 = color::color_cast::<Rgb<f32>, _>(&c);
+        let c3 = color::color_cast::<Rgb<u8>, _>(&c2);
+
+        assert_ulps_eq!(c2.red(), 127.0 / 255.0);
+        assert_ulps_eq!(c2.green(), 0.0);
+        assert_ulps_eq!(c2.blue(), 1.0);
+
+        assert_eq!(c3.red(), 127);
+        assert_eq!(c3.green(), 0);
+        assert_eq!(c3.blue(), 255);
+
+        let c4 = color::color_cast::<Rgb<u16>, _>(&c);
+        assert_eq!(c4.red(), 32639);
+        assert_eq!(c4.green(), 0);
+        assert_eq!(c4.blue(), 65535);
+    }
+
+    #[test]
+    fn test_from_hex() {
+        assert_eq!(Rgb::from_hex("#ff0033").unwrap(), Rgb::from_channels(255u8, 0, 51));
+        assert_eq!(Rgb::from_hex("ff0033").unwrap(), Rgb::from_channels(255u8, 0, 51));
+        assert_eq!(Rgb::from_hex("#f03").unwrap(), Rgb::from_channels(255u8, 0, 51));
+        assert_eq!("#f03".parse::<Rgb<u8>>().unwrap(), Rgb::from_channels(255u8, 0, 51));
+
+        assert_eq!(Rgb::from_hex("#ff003"), Err(FromHexError::InvalidLength));
+        assert_eq!(Rgb::from_hex("#gg0033"), Err(FromHexError::InvalidDigit));
+
+        // Non-ASCII input whose byte length happens to match a valid digit count must be
+        // rejected, not panic on a mid-codepoint slice boundary.
+        assert_eq!(Rgb::from_hex("a\u{20ac}00"), Err(FromHexError::InvalidDigit));
+        assert_eq!(Rgb::from_hex("\u{20ac}"), Err(FromHexError::InvalidDigit));
+
+        // Only a single leading `#` is stripped; a malformed literal with extras is rejected.
+        assert_eq!(Rgb::from_hex("##f03"), Err(FromHexError::InvalidLength));
+    }
+
+    #[test]
+    fn test_to_hex_string() {
+        assert_eq!(Rgb::from_channels(255u8, 0, 51).to_hex_string(), "#ff0033");
+    }
+
+    #[test]
+    fn test_alpha_from_hex() {
+        let expected = Alpha::from_color_and_alpha(Rgb::from_channels(255u8, 0, 51), 128u8);
+        assert_eq!(Alpha::from_hex("#ff003380").unwrap(), expected);
+        assert_eq!(Alpha::from_hex("ff003380").unwrap(), expected);
+        assert_eq!("#ff003380".parse::<Alpha<Rgb<u8>, u8>>().unwrap(), expected);
+
+        assert_eq!(Alpha::<Rgb<u8>, u8>::from_hex("#ff0033"),
+                   Err(FromHexError::InvalidLength));
+        assert_eq!(Alpha::<Rgb<u8>, u8>::from_hex("#gg003380"),
+                   Err(FromHexError::InvalidDigit));
+
+        // Non-ASCII input whose byte length happens to match a valid digit count must be
+        // rejected, not panic on a mid-codepoint slice boundary.
+        assert_eq!(Alpha::<Rgb<u8>, u8>::from_hex("a\u{20ac}0033"),
+                   Err(FromHexError::InvalidDigit));
+
+        // Only a single leading `#` is stripped; a malformed literal with extras is rejected.
+        assert_eq!(Alpha::<Rgb<u8>, u8>::from_hex("##ff003380"),
+                   Err(FromHexError::InvalidLength));
+    }
+
+    #[test]
+    fn test_alpha_to_hex_string() {
+        let c = Alpha::from_color_and_alpha(Rgb::from_channels(255u8, 0, 51), 128u8);
+        assert_eq!(c.to_hex_string(), "#ff003380");
+    }
 }
@@ -0,0 +1,47 @@
+use xyz::Xyz;
+
+/// A standard CIE illuminant, expressed as its reference white XYZ tristimulus values
+/// (2-degree observer, normalized so `Y = 1.0`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WhitePoint {
+    /// Incandescent / tungsten light.
+    A,
+    /// Average / North sky daylight.
+    C,
+    /// Horizon light.
+    D50,
+    /// Mid-morning / mid-afternoon daylight.
+    D55,
+    /// Noon daylight, the sRGB/video reference white.
+    D65,
+    /// North sky daylight.
+    D75,
+    /// Equal-energy illuminant.
+    E,
+}
+
+impl WhitePoint {
+    /// The reference white of this illuminant as an XYZ tristimulus value.
+    pub fn get_xyz(&self) -> Xyz<f64> {
+        match *self {
+            WhitePoint::A => Xyz::from_channels(1.09850, 1.00000, 0.35585),
+            WhitePoint::C => Xyz::from_channels(0.98074, 1.00000, 1.18232),
+            WhitePoint::D50 => Xyz::from_channels(0.96422, 1.00000, 0.82521),
+            WhitePoint::D55 => Xyz::from_channels(0.95682, 1.00000, 0.92149),
+            WhitePoint::D65 => Xyz::from_channels(0.95047, 1.00000, 1.08883),
+            WhitePoint::D75 => Xyz::from_channels(0.94972, 1.00000, 1.22638),
+            WhitePoint::E => Xyz::from_channels(1.00000, 1.00000, 1.00000),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_get_xyz() {
+        assert_eq!(WhitePoint::E.get_xyz(), Xyz::from_channels(1.0, 1.0, 1.0));
+        assert_eq!(WhitePoint::D65.get_xyz(), Xyz::from_channels(0.95047, 1.00000, 1.08883));
+    }
+}
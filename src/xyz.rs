@@ -0,0 +1,134 @@
+use std::fmt;
+use num;
+use approx;
+use color::{Color, Color3, Lerp};
+
+pub struct XyzTag;
+
+/// A color in the CIE 1931 XYZ tristimulus space.
+///
+/// Unlike `Rgb`, XYZ channels aren't bounded to a `[0, 1]`-style range (a color under a bright
+/// white point can have a `Y` well above `1.0`), so they're stored as plain scalars rather than
+/// `BoundedChannel`s.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct Xyz<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Xyz<T>
+    where T: Clone
+{
+    pub fn from_channels(x: T, y: T, z: T) -> Self {
+        Xyz { x: x, y: y, z: z }
+    }
+    pub fn x(&self) -> T {
+        self.x.clone()
+    }
+    pub fn y(&self) -> T {
+        self.y.clone()
+    }
+    pub fn z(&self) -> T {
+        self.z.clone()
+    }
+}
+
+impl<T> Color for Xyz<T>
+    where T: Clone + PartialEq
+{
+    type Tag = XyzTag;
+    type ChannelsTuple = (T, T, T);
+
+    #[inline]
+    fn num_channels() -> u32 {
+        3
+    }
+    fn from_tuple(values: Self::ChannelsTuple) -> Self {
+        Xyz::from_channels(values.0, values.1, values.2)
+    }
+    fn to_tuple(self) -> Self::ChannelsTuple {
+        (self.x, self.y, self.z)
+    }
+}
+
+impl<T> Color3 for Xyz<T> where T: Clone + PartialEq {}
+
+impl<T> Lerp for Xyz<T>
+    where T: Clone + PartialEq + Lerp
+{
+    type Position = <T as Lerp>::Position;
+    impl_color_lerp_square!(Xyz {x, y, z});
+}
+
+// `ApproxEq` and `Default` stay hand-rolled rather than going through `impl_approx_eq!`/
+// `impl_color_default!` (see `ycbcr::bare_ycbcr`): those macros are built around fields that
+// are all crate `ColorChannel` wrappers, reading each one's `.value()` or rebuilding it via a
+// named wrapper type. `Xyz`'s `x`/`y`/`z` are plain `T` (see the struct doc comment), not
+// `ColorChannel`s, so there's no wrapper to thread through either macro.
+impl<T> approx::ApproxEq for Xyz<T>
+    where T: Clone + PartialEq + approx::ApproxEq,
+          T::Epsilon: Clone
+{
+    type Epsilon = T::Epsilon;
+
+    fn default_epsilon() -> Self::Epsilon {
+        T::default_epsilon()
+    }
+    fn default_max_relative() -> Self::Epsilon {
+        T::default_max_relative()
+    }
+    fn default_max_ulps() -> u32 {
+        T::default_max_ulps()
+    }
+    fn relative_eq(&self,
+                    other: &Self,
+                    epsilon: Self::Epsilon,
+                    max_relative: Self::Epsilon)
+                    -> bool {
+        self.x.relative_eq(&other.x, epsilon.clone(), max_relative.clone()) &&
+        self.y.relative_eq(&other.y, epsilon.clone(), max_relative.clone()) &&
+        self.z.relative_eq(&other.z, epsilon, max_relative)
+    }
+    fn ulps_eq(&self, other: &Self, epsilon: Self::Epsilon, max_ulps: u32) -> bool {
+        self.x.ulps_eq(&other.x, epsilon.clone(), max_ulps) &&
+        self.y.ulps_eq(&other.y, epsilon.clone(), max_ulps) &&
+        self.z.ulps_eq(&other.z, epsilon, max_ulps)
+    }
+}
+
+impl<T> Default for Xyz<T>
+    where T: Clone + PartialEq + num::Zero
+{
+    fn default() -> Self {
+        Xyz::from_channels(T::zero(), T::zero(), T::zero())
+    }
+}
+
+impl<T> fmt::Display for Xyz<T>
+    where T: Clone + PartialEq + fmt::Display
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Xyz({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_construct() {
+        let c = Xyz::from_channels(0.1_f64, 0.2, 0.3);
+        assert_eq!(c.x(), 0.1);
+        assert_eq!(c.y(), 0.2);
+        assert_eq!(c.z(), 0.3);
+    }
+
+    #[test]
+    fn test_lerp() {
+        let c1 = Xyz::from_channels(0.0_f64, 0.0, 0.0);
+        let c2 = Xyz::from_channels(1.0_f64, 1.0, 1.0);
+        assert_ulps_eq!(c1.lerp(&c2, 0.5_f64), Xyz::from_channels(0.5_f64, 0.5, 0.5));
+    }
+}